@@ -6,6 +6,7 @@ use sgx_crypto::cmac::MacTag;
 use sgx_crypto::key_exchange::DHKEPublicKey;
 use ra_common::msg::{Gid, Quote, RaMsg0, RaMsg1, RaMsg2, RaMsg3, RaMsg4};
 use ra_common::Stream;
+use ra_tls::RaTlsResult;
 use crate::error::ClientRaError;
 use crate::ClientRaResult;
 
@@ -78,7 +79,26 @@ impl ClientRaContext {
         Ok(())
     }
 
-    /// ExGID = 0 means IAS will be used for remote attestation. This function only 
+    /// RA-TLS mode: fetch a quote for the enclave the same way [`Self::do_attestation`]
+    /// does, then submit it to IAS ourselves and hand the verification report to the
+    /// enclave so it can embed both in its self-signed certificate. There is no SP
+    /// relay in this mode — we talk to IAS directly.
+    #[tokio::main]
+    pub async fn do_attestation_ra_tls(
+        &self,
+        spid: Vec<u8>,
+        subscription_key: &str,
+        mut enclave_stream: &mut impl Stream,
+    ) -> RaTlsResult<()> {
+        let sig_rl = Vec::with_capacity(0);
+        let quote = Self::get_quote(&self.aesm_client, spid, sig_rl, enclave_stream)
+            .map_err(|e| ra_tls::RaTlsError::Attestation(e.to_string()))?;
+        let ias_report = ra_tls::ias::fetch_verification_report(&quote, subscription_key).await?;
+        bincode::serialize_into(&mut enclave_stream, &ias_report)?;
+        Ok(())
+    }
+
+    /// ExGID = 0 means IAS will be used for remote attestation. This function only
     /// returns 0 for now.
     pub fn get_extended_epid_group_id(&self) -> RaMsg0 {
         RaMsg0 { exgid: 0 }
@@ -8,6 +8,7 @@ use sgx_crypto::cmac::{Cmac, MacTag};
 use sgx_crypto::digest::sha256;
 use ra_common::{Stream, derive_secret_keys};
 use ra_common::msg::{Quote, RaMsg2, RaMsg3, RaMsg4};
+use ra_tls::{IasVerificationReport, RaTlsKeyPair, RaTlsResult};
 use crate::error::EnclaveRaError;
 use crate::EnclaveRaResult;
 use crate::local_attestation;
@@ -84,6 +85,26 @@ impl EnclaveRaContext {
             Ok((sk, mk))
         }
 
+    /// RA-TLS mode: generate an ephemeral TLS keypair, bind it into a quote's
+    /// `report_data`, and build a self-signed certificate carrying that quote plus the
+    /// IAS verification report `client_stream` sends back for it. Unlike
+    /// [`Self::do_attestation`], no SP relay is involved — `client_stream` only needs to
+    /// ferry bytes between this enclave and the Quote Enclave (via `get_quote`) and
+    /// forward the IAS report it fetched on our behalf.
+    pub fn do_attestation_ra_tls(
+        &self,
+        client_stream: &mut impl Stream,
+    ) -> RaTlsResult<(RaTlsKeyPair, Vec<u8>)> {
+        let rng = RandomState::new();
+        let keypair = RaTlsKeyPair::generate(&rng)?;
+        let report_data = keypair.report_data()?;
+        let quote = Self::get_quote(&report_data, client_stream)
+            .map_err(|e| ra_tls::RaTlsError::Attestation(e.to_string()))?;
+        let ias_report: IasVerificationReport = bincode::deserialize_from(&mut *client_stream)?;
+        let cert_der = ra_tls::build_self_signed_cert(&keypair, &quote, &ias_report)?;
+        Ok((keypair, cert_der))
+    }
+
     /// Get quote from Quote Enclave. The length of report_data must be <= 64 bytes.
     pub fn get_quote(report_data: &[u8],
                      client_stream: &mut impl Stream) -> EnclaveRaResult<Quote> {
@@ -0,0 +1,96 @@
+use aes_gcm::aead::{Aead, KeyInit, Payload};
+use aes_gcm::Aes128Gcm;
+use byteorder::{BigEndian, ByteOrder};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+use crate::channel::{Direction, SEQ_LEN};
+use crate::error::{SecureChannelError, SecureChannelResult, MAX_RECORD_LEN};
+
+/// Async counterpart of [`crate::SecureChannel`] for stream types that are
+/// `AsyncRead + AsyncWrite` instead of the blocking [`ra_common::Stream`]. Shares the
+/// same wire format and nonce derivation, so either side of a connection can be sync or
+/// async independently of the other.
+pub struct AsyncSecureChannel<S> {
+    stream: S,
+    cipher: Aes128Gcm,
+    direction: Direction,
+    send_seq: u64,
+    recv_seq: u64,
+}
+
+impl<S: AsyncRead + AsyncWrite + Unpin> AsyncSecureChannel<S> {
+    pub fn new(stream: S, master_key: &[u8; 16], is_initiator: bool) -> Self {
+        Self {
+            stream,
+            cipher: Aes128Gcm::new_from_slice(master_key).expect("master key is 16 bytes"),
+            direction: Direction::from_is_initiator(is_initiator),
+            send_seq: 0,
+            recv_seq: 0,
+        }
+    }
+
+    pub async fn write(&mut self, plaintext: &[u8]) -> SecureChannelResult<()> {
+        if self.send_seq == u64::MAX {
+            return Err(SecureChannelError::SequenceExhausted);
+        }
+        let seq = self.send_seq;
+        let nonce = crate::channel::nonce(self.direction, seq);
+        let ciphertext = self
+            .cipher
+            .encrypt(
+                &nonce,
+                Payload {
+                    msg: plaintext,
+                    aad: &seq.to_be_bytes(),
+                },
+            )
+            .map_err(|_| SecureChannelError::AuthenticationFailed)?;
+
+        let body_len = (SEQ_LEN + ciphertext.len()) as u32;
+        let mut length_prefix = [0u8; 4];
+        BigEndian::write_u32(&mut length_prefix, body_len);
+
+        self.stream.write_all(&length_prefix).await?;
+        self.stream.write_all(&seq.to_be_bytes()).await?;
+        self.stream.write_all(&ciphertext).await?;
+        self.send_seq += 1;
+        Ok(())
+    }
+
+    pub async fn read(&mut self) -> SecureChannelResult<Vec<u8>> {
+        let mut length_prefix = [0u8; 4];
+        self.stream.read_exact(&mut length_prefix).await?;
+        let body_len = BigEndian::read_u32(&length_prefix);
+        if body_len > MAX_RECORD_LEN || (body_len as usize) < SEQ_LEN + 16 {
+            return Err(SecureChannelError::RecordTooLarge(body_len));
+        }
+
+        let mut body = vec![0u8; body_len as usize];
+        self.stream.read_exact(&mut body).await?;
+
+        let mut seq_bytes = [0u8; SEQ_LEN];
+        seq_bytes.copy_from_slice(&body[..SEQ_LEN]);
+        let seq = u64::from_be_bytes(seq_bytes);
+        if seq != self.recv_seq {
+            return Err(SecureChannelError::OutOfOrder {
+                expected: self.recv_seq,
+                got: seq,
+            });
+        }
+
+        let nonce = crate::channel::nonce(self.direction.flipped(), seq);
+        let plaintext = self
+            .cipher
+            .decrypt(
+                &nonce,
+                Payload {
+                    msg: &body[SEQ_LEN..],
+                    aad: &seq_bytes,
+                },
+            )
+            .map_err(|_| SecureChannelError::AuthenticationFailed)?;
+
+        self.recv_seq += 1;
+        Ok(plaintext)
+    }
+}
@@ -0,0 +1,152 @@
+use aes_gcm::aead::{Aead, KeyInit, Payload};
+use aes_gcm::{Aes128Gcm, Nonce};
+use byteorder::{BigEndian, ByteOrder};
+use sgx_crypto::cmac::MacTag;
+use ra_common::Stream;
+
+use crate::error::{SecureChannelError, SecureChannelResult, MAX_RECORD_LEN};
+
+const LENGTH_PREFIX_LEN: usize = 4;
+pub(crate) const SEQ_LEN: usize = 8;
+const TAG_LEN: usize = 16;
+
+/// Which side of the channel encrypted a given direction of traffic. Mixed into the
+/// AEAD nonce alongside the sequence number so that the initiator->responder and
+/// responder->initiator directions never share a nonce even when their counters collide.
+#[derive(Clone, Copy)]
+pub(crate) enum Direction {
+    Initiator,
+    Responder,
+}
+
+impl Direction {
+    pub(crate) fn from_is_initiator(is_initiator: bool) -> Self {
+        if is_initiator {
+            Direction::Initiator
+        } else {
+            Direction::Responder
+        }
+    }
+
+    fn byte(self) -> u8 {
+        match self {
+            Direction::Initiator => 0,
+            Direction::Responder => 1,
+        }
+    }
+
+    pub(crate) fn flipped(self) -> Self {
+        match self {
+            Direction::Initiator => Direction::Responder,
+            Direction::Responder => Direction::Initiator,
+        }
+    }
+}
+
+pub(crate) fn nonce(direction: Direction, seq: u64) -> Nonce {
+    let mut bytes = [0u8; 12];
+    bytes[..SEQ_LEN].copy_from_slice(&seq.to_be_bytes());
+    bytes[SEQ_LEN] = direction.byte();
+    *Nonce::from_slice(&bytes)
+}
+
+/// A confidential, authenticated, replay-protected channel wrapping a [`Stream`],
+/// derived from the `master_key` an [`ra_enclave::EnclaveRaContext`]/[`ra_sp::SpRaContext`]
+/// pair agree on during `do_attestation`. Keys AES-128-GCM with `master_key`, derives a
+/// fresh 96-bit nonce per record from a monotonically increasing 64-bit sequence number
+/// plus a direction byte, and rejects any record whose sequence number isn't exactly the
+/// next one expected in that direction.
+pub struct SecureChannel<S: Stream> {
+    stream: S,
+    cipher: Aes128Gcm,
+    direction: Direction,
+    send_seq: u64,
+    recv_seq: u64,
+}
+
+impl<S: Stream> SecureChannel<S> {
+    /// `is_initiator` should be `true` on exactly one side of the channel (by
+    /// convention, whichever side sent MSG1 during attestation) so the two peers don't
+    /// derive the same nonces for their respective send directions.
+    pub fn new(stream: S, master_key: &MacTag, is_initiator: bool) -> Self {
+        Self {
+            stream,
+            cipher: Aes128Gcm::new_from_slice(master_key).expect("master key is 16 bytes"),
+            direction: Direction::from_is_initiator(is_initiator),
+            send_seq: 0,
+            recv_seq: 0,
+        }
+    }
+
+    /// Encrypt `plaintext` and write it to the underlying stream as one length-prefixed
+    /// record: `[4-byte big-endian length][8-byte sequence number][ciphertext || tag]`.
+    pub fn write(&mut self, plaintext: &[u8]) -> SecureChannelResult<()> {
+        if self.send_seq == u64::MAX {
+            return Err(SecureChannelError::SequenceExhausted);
+        }
+        let seq = self.send_seq;
+        let nonce = nonce(self.direction, seq);
+        let ciphertext = self
+            .cipher
+            .encrypt(
+                &nonce,
+                Payload {
+                    msg: plaintext,
+                    aad: &seq.to_be_bytes(),
+                },
+            )
+            .map_err(|_| SecureChannelError::AuthenticationFailed)?;
+
+        let mut record = Vec::with_capacity(LENGTH_PREFIX_LEN + SEQ_LEN + ciphertext.len());
+        let body_len = (SEQ_LEN + ciphertext.len()) as u32;
+        let mut length_prefix = [0u8; LENGTH_PREFIX_LEN];
+        BigEndian::write_u32(&mut length_prefix, body_len);
+        record.extend_from_slice(&length_prefix);
+        record.extend_from_slice(&seq.to_be_bytes());
+        record.extend_from_slice(&ciphertext);
+
+        self.stream.write_all(&record)?;
+        self.send_seq += 1;
+        Ok(())
+    }
+
+    /// Read one record and decrypt it, rejecting any record whose sequence number is
+    /// not exactly the next one expected — this is what makes out-of-order delivery and
+    /// replayed records fail closed instead of silently decrypting.
+    pub fn read(&mut self) -> SecureChannelResult<Vec<u8>> {
+        let mut length_prefix = [0u8; LENGTH_PREFIX_LEN];
+        self.stream.read_exact(&mut length_prefix)?;
+        let body_len = BigEndian::read_u32(&length_prefix);
+        if body_len > MAX_RECORD_LEN || (body_len as usize) < SEQ_LEN + TAG_LEN {
+            return Err(SecureChannelError::RecordTooLarge(body_len));
+        }
+
+        let mut body = vec![0u8; body_len as usize];
+        self.stream.read_exact(&mut body)?;
+
+        let mut seq_bytes = [0u8; SEQ_LEN];
+        seq_bytes.copy_from_slice(&body[..SEQ_LEN]);
+        let seq = u64::from_be_bytes(seq_bytes);
+        if seq != self.recv_seq {
+            return Err(SecureChannelError::OutOfOrder {
+                expected: self.recv_seq,
+                got: seq,
+            });
+        }
+
+        let nonce = nonce(self.direction.flipped(), seq);
+        let plaintext = self
+            .cipher
+            .decrypt(
+                &nonce,
+                Payload {
+                    msg: &body[SEQ_LEN..],
+                    aad: &seq_bytes,
+                },
+            )
+            .map_err(|_| SecureChannelError::AuthenticationFailed)?;
+
+        self.recv_seq += 1;
+        Ok(plaintext)
+    }
+}
@@ -0,0 +1,22 @@
+#[derive(Debug, thiserror::Error)]
+pub enum SecureChannelError {
+    #[error("record sequence number {got} is out of order, expected {expected}")]
+    OutOfOrder { expected: u64, got: u64 },
+
+    #[error("record length {0} is shorter than a sequence number plus tag or exceeds the maximum allowed record size")]
+    RecordTooLarge(u32),
+
+    #[error("AEAD authentication failed, record may have been tampered with")]
+    AuthenticationFailed,
+
+    #[error("sequence counter exhausted")]
+    SequenceExhausted,
+
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+pub type SecureChannelResult<T> = Result<T, SecureChannelError>;
+
+/// Records larger than this are rejected before any buffer is allocated for them.
+pub const MAX_RECORD_LEN: u32 = 16 * 1024 * 1024;
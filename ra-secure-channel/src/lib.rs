@@ -0,0 +1,12 @@
+//! A confidential, authenticated record channel keyed by the `master_key` an
+//! [`ra_enclave::EnclaveRaContext`] and [`ra_sp::SpRaContext`] agree on during
+//! `do_attestation`, so callers don't
+//! have to reinvent key handling for the traffic that follows a successful attestation.
+
+mod async_channel;
+mod channel;
+mod error;
+
+pub use async_channel::AsyncSecureChannel;
+pub use channel::SecureChannel;
+pub use error::{SecureChannelError, SecureChannelResult};
@@ -2,7 +2,6 @@ use std::fs::File;
 use std::io::Write;
 use std::path::Path;
 use std::convert::TryInto;
-use byteorder::{ReadBytesExt, LittleEndian};
 use sgxs::sigstruct;
 use sgx_crypto::random::RandomState;
 use sgx_crypto::key_exchange::OneWayAuthenticatedDHKE;
@@ -15,13 +14,15 @@ use ra_common::{derive_secret_keys, Stream};
 use crate::ias::{IasClient};
 use crate::config::SpConfig;
 use crate::error::SpRaError;
+use crate::quote_verifier::{IasQuoteVerifier, QuoteVerifier};
 use crate::{SpRaResult, AttestationResult};
 
-pub struct SpRaContext {
+pub struct SpRaContext<V: QuoteVerifier = IasQuoteVerifier> {
     config: SpConfig,
     sigstruct: sigstruct::Sigstruct,
-    ias_client: IasClient, 
-    sp_private_key: SigningKey, 
+    ias_client: IasClient,
+    quote_verifier: V,
+    sp_private_key: SigningKey,
     rng: RandomState,
     key_exchange: Option<OneWayAuthenticatedDHKE>,
     verification_digest: Option<Sha256Digest>,
@@ -29,8 +30,28 @@ pub struct SpRaContext {
     sk_mk: Option<(MacTag, MacTag)>,
 }
 
-impl SpRaContext {
-    pub fn init(mut config: SpConfig) -> SpRaResult<Self> {
+impl SpRaContext<IasQuoteVerifier> {
+    pub fn init(config: SpConfig) -> SpRaResult<Self> {
+        let verifier_cert = X509Cert::new_from_pem_file(
+            Path::new(&config.ias_root_cert_pem_path))?;
+        let quote_verifier = IasQuoteVerifier {
+            ias_client: IasClient::new(verifier_cert),
+            primary_subscription_key: config.primary_subscription_key.clone(),
+            quote_trust_options: config.quote_trust_options.clone(),
+            pse_trust_options: config.pse_trust_options.clone(),
+        };
+        Self::with_quote_verifier(config, quote_verifier)
+    }
+}
+
+impl<V: QuoteVerifier> SpRaContext<V> {
+    /// Build a context around a `quote_verifier` — the default [`IasQuoteVerifier`] via
+    /// [`SpRaContext::init`], or e.g. an [`crate::quote_verifier::OfflineQuoteVerifier`]
+    /// for deployments that pre-fetch collateral or don't use IAS at all. `sig_rl`
+    /// fetching still goes through `config`'s IAS credentials regardless, since that's
+    /// part of the EPID quote protocol itself rather than the trust decision this type
+    /// is generic over.
+    pub fn with_quote_verifier(config: SpConfig, quote_verifier: V) -> SpRaResult<Self> {
         assert!(config.linkable, "Only Linkable Quote supported");
         assert!(!config.random_nonce, "Random nonces not supported");
         assert!(!config.use_platform_service, "Platform service not supported");
@@ -40,10 +61,6 @@ impl SpRaContext {
             eprintln!("=============================================");
         }
 
-        // Preparing for binary search
-        config.quote_trust_options.sort();
-        config.pse_trust_options.as_mut().map(|v| v.sort());
-
         let sp_private_key = SigningKey::new_from_pem_file(
             Path::new(&config.sp_private_key_pem_path))?;
 
@@ -60,18 +77,19 @@ impl SpRaContext {
             config,
             sigstruct,
             ias_client: IasClient::new(cert),
+            quote_verifier,
             sp_private_key,
             rng,
             key_exchange: Some(key_exchange),
-            verification_digest: None, 
+            verification_digest: None,
             smk: None,
             sk_mk: None,
         })
     }
 
     #[tokio::main]
-    pub async fn do_attestation(mut self, 
-                                mut client_stream: &mut impl Stream) 
+    pub async fn do_attestation(mut self,
+                                mut client_stream: &mut impl Stream)
         -> SpRaResult<AttestationResult> {
             // Not using MSG0 for now.
             let _msg0: RaMsg0 = bincode::deserialize_from(&mut client_stream)?;
@@ -184,54 +202,22 @@ impl SpRaContext {
                 return Err(SpRaError::IntegrityError);
             }
 
-            // Verify attestation evidence
-            // TODO: use the secondary key as well
-            let attestation_result = self.ias_client
-                .verify_attestation_evidence(
-                    &msg3.quote, 
-                    &self.config.primary_subscription_key).await?;
+            // Verify the quote: measurements, and however `self.quote_verifier` decides
+            // to establish trust (by default, an IAS round trip).
+            let verdict = self.quote_verifier
+                .verify(&msg3.quote, &self.sigstruct).await?;
 
             if cfg!(feature = "verbose") {
-                eprintln!("==============Attestation Result==============");
-                eprintln!("{:#?}", attestation_result);
-                eprintln!("==============================================");
-            }
-
-            // Verify enclave identity
-            let mrenclave = &msg3.quote[112..144];
-            let mrsigner = &msg3.quote[176..208];
-            let isvprodid = (&msg3.quote[304..306]).read_u16::<LittleEndian>().unwrap();
-            let isvsvn = (&msg3.quote[306..308]).read_u16::<LittleEndian>().unwrap();
-            if mrenclave != &self.sigstruct.enclavehash[..] ||
-                mrsigner != &sha256(&self.sigstruct.modulus[..])[..] ||
-                    isvprodid != self.sigstruct.isvprodid ||
-                    isvsvn != self.sigstruct.isvsvn {
-                        return Err(SpRaError::SigstructMismatched);
-                    }
-
-            // Make sure the enclave is not in debug mode in production
-            let attribute_flags = &self.sigstruct.attributes.flags;
-            if cfg!(not(debug_assertions)) {
-                if (&sgx_isa::AttributesFlags::DEBUG).intersects(*attribute_flags) {
-                    return Err(SpRaError::EnclaveInDebugMode);
-                }
+                eprintln!("==============Quote Verdict==============");
+                eprintln!("{:#?}", verdict);
+                eprintln!("==========================================");
             }
 
-            // Decide whether to trust enclave
-            let quote_status = attestation_result.isv_enclave_quote_status.clone();
-            let pse_manifest_status = attestation_result.pse_manifest_status.clone();
-            let is_enclave_trusted = (quote_status == "OK") || 
-                self.config.quote_trust_options.binary_search(&quote_status).is_ok();
-            let is_pse_manifest_trusted = pse_manifest_status.map(
-                |status| (status == "OK") ||
-                self.config.pse_trust_options.as_ref().unwrap().binary_search(&status)
-                .is_ok()); 
-
             Ok((RaMsg4 {
-                is_enclave_trusted,
-                is_pse_manifest_trusted,
-                pib: attestation_result.platform_info_blob,
+                is_enclave_trusted: verdict.is_enclave_trusted,
+                is_pse_manifest_trusted: verdict.is_pse_manifest_trusted,
+                pib: verdict.pib,
             },
-            attestation_result.epid_pseudonym))
+            verdict.epid_pseudonym))
         }
 }
\ No newline at end of file
@@ -0,0 +1,34 @@
+#[derive(Debug, thiserror::Error)]
+pub enum SpRaError {
+    #[error("integrity check failed")]
+    IntegrityError,
+
+    #[error("quote measurements do not match the loaded sigstruct")]
+    SigstructMismatched,
+
+    #[error("enclave is running in debug mode")]
+    EnclaveInDebugMode,
+
+    #[error("enclave is not trusted")]
+    EnclaveNotTrusted,
+
+    #[error("IAS verification report signature is invalid")]
+    ReportSignatureInvalid,
+
+    #[error(transparent)]
+    Ias(#[from] crate::ias::IasError),
+
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("sgx crypto error: {0}")]
+    SgxCrypto(#[from] sgx_crypto::error::Error),
+
+    #[error("sigstruct error: {0}")]
+    Sigstruct(#[from] sgxs::sigstruct::Error),
+
+    #[error("bincode error: {0}")]
+    Bincode(#[from] bincode::Error),
+}
+
+pub type SpRaResult<T> = Result<T, SpRaError>;
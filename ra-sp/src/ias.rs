@@ -0,0 +1,150 @@
+use sgx_crypto::certificate::X509Cert;
+use sgx_crypto::rsa::RsaPublicKey;
+use ra_common::msg::{Gid, Quote};
+use crate::error::SpRaError;
+
+const IAS_SIGRL_BASE_URL: &str = "https://api.trustedservices.intel.com/sgx/dev/attestation/v4/sigrl";
+const IAS_REPORT_URL: &str = "https://api.trustedservices.intel.com/sgx/dev/attestation/v4/report";
+
+#[derive(Debug, thiserror::Error)]
+pub enum IasError {
+    #[error("IAS request failed: {0}")]
+    Request(#[from] reqwest::Error),
+
+    #[error("IAS response is missing the {0} header")]
+    MissingHeader(&'static str),
+
+    #[error("IAS response header could not be decoded: {0}")]
+    MalformedHeader(String),
+
+    #[error("IAS response body could not be parsed: {0}")]
+    MalformedBody(#[from] serde_json::Error),
+
+    #[error("IAS report nonce does not match the one we sent")]
+    NonceMismatch,
+}
+
+/// Attestation verdict returned by IAS for a single quote. It holds only the parsed
+/// report body; the signature and signing certificate are checked by
+/// [`IasClient::verify_attestation_evidence`] before this is parsed, and are not kept.
+#[derive(Debug, Clone, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AttestationResult {
+    pub nonce: Option<String>,
+    pub id: String,
+    pub isv_enclave_quote_status: String,
+    pub epid_pseudonym: Option<String>,
+    pub platform_info_blob: Option<String>,
+    pub pse_manifest_status: Option<String>,
+}
+
+pub struct IasClient {
+    http: reqwest::Client,
+    ias_root_cert: X509Cert,
+}
+
+impl IasClient {
+    pub fn new(ias_root_cert: X509Cert) -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            ias_root_cert,
+        }
+    }
+
+    pub async fn get_sig_rl(
+        &self,
+        gid: &Gid,
+        subscription_key: &str,
+    ) -> Result<Option<Vec<u8>>, IasError> {
+        let url = format!("{}/{}", IAS_SIGRL_BASE_URL, hex::encode_upper(gid));
+        let response = self
+            .http
+            .get(&url)
+            .header("Ocp-Apim-Subscription-Key", subscription_key)
+            .send()
+            .await?;
+        let body = response.text().await?;
+        if body.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(base64::decode(&body).map_err(|e| {
+                IasError::MalformedHeader(e.to_string())
+            })?))
+        }
+    }
+
+    /// Submit `quote` to IAS and verify that the report IAS sent back is genuine: the
+    /// `X-IASReport-Signature`/`X-IASReport-Signing-Certificate` headers chain to
+    /// `self.ias_root_cert` and the RSA-PKCS1-SHA256 signature over the raw report body
+    /// checks out, and the report's `nonce` echoes the one we sent — closing the gap
+    /// where a man-in-the-middle on the IAS connection could forge an "OK" verdict.
+    pub async fn verify_attestation_evidence(
+        &self,
+        quote: &Quote,
+        subscription_key: &str,
+    ) -> Result<AttestationResult, SpRaError> {
+        let nonce = hex::encode(sgx_crypto::random::RandomState::new().bytes(16));
+        let response = self
+            .http
+            .post(IAS_REPORT_URL)
+            .header("Ocp-Apim-Subscription-Key", subscription_key)
+            .json(&serde_json::json!({
+                "isvEnclaveQuote": base64::encode(&quote[..]),
+                "nonce": nonce,
+            }))
+            .send()
+            .await
+            .map_err(IasError::Request)?;
+
+        let signature = base64::decode(
+            response
+                .headers()
+                .get("X-IASReport-Signature")
+                .ok_or(IasError::MissingHeader("X-IASReport-Signature"))?
+                .to_str()
+                .map_err(|e| IasError::MalformedHeader(e.to_string()))?,
+        )
+        .map_err(|e| IasError::MalformedHeader(e.to_string()))?;
+
+        let signing_cert_chain = percent_encoding::percent_decode_str(
+            response
+                .headers()
+                .get("X-IASReport-Signing-Certificate")
+                .ok_or(IasError::MissingHeader("X-IASReport-Signing-Certificate"))?
+                .to_str()
+                .map_err(|e| IasError::MalformedHeader(e.to_string()))?,
+        )
+        .collect::<Vec<u8>>();
+
+        let report_body = response.bytes().await.map_err(IasError::Request)?;
+
+        self.verify_report_signature(&report_body, &signature, &signing_cert_chain)?;
+
+        let attestation_result: AttestationResult =
+            serde_json::from_slice(&report_body).map_err(IasError::MalformedBody)?;
+
+        if attestation_result.nonce.as_deref() != Some(nonce.as_str()) {
+            return Err(IasError::NonceMismatch.into());
+        }
+
+        Ok(attestation_result)
+    }
+
+    fn verify_report_signature(
+        &self,
+        report_body: &[u8],
+        signature: &[u8],
+        signing_cert_chain_pem: &[u8],
+    ) -> Result<(), SpRaError> {
+        let signing_cert = X509Cert::new_from_pem(signing_cert_chain_pem)?;
+        signing_cert
+            .verify_chain(&self.ias_root_cert)
+            .map_err(|_| SpRaError::ReportSignatureInvalid)?;
+
+        let public_key = RsaPublicKey::from_cert(&signing_cert)?;
+        public_key
+            .verify_pkcs1_sha256(report_body, signature)
+            .map_err(|_| SpRaError::ReportSignatureInvalid)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,116 @@
+use std::convert::TryInto;
+use byteorder::{LittleEndian, ReadBytesExt};
+use sgx_crypto::digest::sha256;
+use sgxs::sigstruct::Sigstruct;
+use ra_common::msg::Quote;
+
+use crate::error::SpRaResult;
+use crate::ias::IasClient;
+
+/// What a [`QuoteVerifier`] decided about a quote, independent of how it got there
+/// (an IAS round trip, or a purely local check against pre-fetched collateral).
+#[derive(Debug, Clone)]
+pub struct QuoteVerdict {
+    pub is_enclave_trusted: bool,
+    pub is_pse_manifest_trusted: Option<bool>,
+    pub pib: Option<String>,
+    pub epid_pseudonym: Option<String>,
+}
+
+/// A pluggable trust decision for quotes. [`crate::SpRaContext`] is generic over this so
+/// deployments that pre-fetch collateral or use non-IAS flows aren't forced through the
+/// IAS web service — see [`IasQuoteVerifier`] for the default (IAS/EPID) behavior and
+/// [`OfflineQuoteVerifier`] for a fully local one.
+#[async_trait::async_trait]
+pub trait QuoteVerifier {
+    async fn verify(&self, quote: &Quote, expected: &Sigstruct) -> SpRaResult<QuoteVerdict>;
+}
+
+/// Checks the quote's MRENCLAVE/MRSIGNER/ISVPRODID/ISVSVN against `expected`, and that
+/// the enclave isn't running in debug mode outside of a debug build. Both
+/// [`IasQuoteVerifier`] and [`OfflineQuoteVerifier`] use this, since it's the one part of
+/// the decision that's always made locally regardless of where the quote itself came
+/// from.
+fn check_measurements(quote: &Quote, expected: &Sigstruct) -> SpRaResult<()> {
+    let mrenclave = &quote[112..144];
+    let mrsigner = &quote[176..208];
+    let isvprodid = (&quote[304..306]).read_u16::<LittleEndian>().unwrap();
+    let isvsvn = (&quote[306..308]).read_u16::<LittleEndian>().unwrap();
+    let attributes_flags = (&quote[96..104]).read_u64::<LittleEndian>().unwrap();
+
+    if mrenclave != &expected.enclavehash[..]
+        || mrsigner != &sha256(&expected.modulus[..])[..]
+        || isvprodid != expected.isvprodid
+        || isvsvn != expected.isvsvn
+    {
+        return Err(crate::error::SpRaError::SigstructMismatched);
+    }
+
+    if cfg!(not(debug_assertions))
+        && sgx_isa::AttributesFlags::from_bits_truncate(attributes_flags)
+            .intersects(sgx_isa::AttributesFlags::DEBUG)
+    {
+        return Err(crate::error::SpRaError::EnclaveInDebugMode);
+    }
+    Ok(())
+}
+
+/// The original trust decision: submit the quote to IAS and trust whatever
+/// `isv_enclave_quote_status`/`pse_manifest_status` it reports, subject to the
+/// `quote_trust_options`/`pse_trust_options` allowlists.
+pub struct IasQuoteVerifier {
+    pub ias_client: IasClient,
+    pub primary_subscription_key: String,
+    pub quote_trust_options: Vec<String>,
+    pub pse_trust_options: Option<Vec<String>>,
+}
+
+#[async_trait::async_trait]
+impl QuoteVerifier for IasQuoteVerifier {
+    async fn verify(&self, quote: &Quote, expected: &Sigstruct) -> SpRaResult<QuoteVerdict> {
+        check_measurements(quote, expected)?;
+
+        let attestation_result = self
+            .ias_client
+            .verify_attestation_evidence(quote, &self.primary_subscription_key)
+            .await?;
+
+        let quote_status = attestation_result.isv_enclave_quote_status.clone();
+        let pse_manifest_status = attestation_result.pse_manifest_status.clone();
+        let is_enclave_trusted =
+            (quote_status == "OK") || self.quote_trust_options.iter().any(|s| s == &quote_status);
+        let is_pse_manifest_trusted = pse_manifest_status.map(|status| {
+            (status == "OK")
+                || self
+                    .pse_trust_options
+                    .as_ref()
+                    .map(|options| options.iter().any(|s| s == &status))
+                    .unwrap_or(false)
+        });
+
+        Ok(QuoteVerdict {
+            is_enclave_trusted,
+            is_pse_manifest_trusted,
+            pib: attestation_result.platform_info_blob,
+            epid_pseudonym: attestation_result.epid_pseudonym,
+        })
+    }
+}
+
+/// Verifies a quote entirely from its own parsed structure — MRENCLAVE/MRSIGNER/
+/// ISVPRODID/ISVSVN and the debug attribute flag — without calling out to IAS. Useful
+/// for deployments that pre-fetch collateral out of band or don't use IAS/EPID at all.
+pub struct OfflineQuoteVerifier;
+
+#[async_trait::async_trait]
+impl QuoteVerifier for OfflineQuoteVerifier {
+    async fn verify(&self, quote: &Quote, expected: &Sigstruct) -> SpRaResult<QuoteVerdict> {
+        check_measurements(quote, expected)?;
+        Ok(QuoteVerdict {
+            is_enclave_trusted: true,
+            is_pse_manifest_trusted: None,
+            pib: None,
+            epid_pseudonym: None,
+        })
+    }
+}
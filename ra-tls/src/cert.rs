@@ -0,0 +1,83 @@
+use serde::{Deserialize, Serialize};
+use sgx_crypto::certificate::X509Cert;
+use sgx_crypto::digest::sha256;
+use sgx_crypto::random::RandomState;
+use sgx_crypto::signature::{SigningKey, VerificationKey};
+use ra_common::msg::Quote;
+
+use crate::error::RaTlsResult;
+
+/// Private enterprise OID under Intel's arc, reused by Intel's own RA-TLS samples for
+/// carrying the quote (and, here, the IAS verification report) as a custom X.509 v3
+/// extension on a self-signed leaf certificate.
+pub const RA_TLS_QUOTE_OID: &str = "1.2.840.113741.1337.6";
+
+/// The IAS attestation verification report for a single quote, together with enough of
+/// the HTTP response to let a verifier check it was really signed by Intel.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct IasVerificationReport {
+    /// Raw `X-IASReport-Signature` header value (base64-decoded).
+    pub signature: Vec<u8>,
+    /// PEM-encoded `X-IASReport-Signing-Certificate` chain, as sent by IAS.
+    pub signing_cert_chain: Vec<u8>,
+    /// The raw JSON report body the signature above was computed over.
+    pub report_body: Vec<u8>,
+}
+
+/// An ephemeral keypair generated by the enclave for a single RA-TLS session. The
+/// public key's SHA-256 digest is bound into the quote as `report_data`, which is what
+/// lets a verifier trust that the TLS certificate and the quote describe the same key.
+pub struct RaTlsKeyPair {
+    pub signing_key: SigningKey,
+    pub verification_key: VerificationKey,
+}
+
+impl RaTlsKeyPair {
+    pub fn generate(rng: &RandomState) -> RaTlsResult<Self> {
+        let signing_key = SigningKey::generate(rng)?;
+        let verification_key = signing_key.verification_key();
+        Ok(Self {
+            signing_key,
+            verification_key,
+        })
+    }
+
+    pub fn public_key_der(&self) -> RaTlsResult<Vec<u8>> {
+        Ok(self.verification_key.to_der()?)
+    }
+
+    /// `SHA256(DER-encoded public key)`, zero-padded out to the 64 bytes `get_quote`
+    /// expects for `report_data`.
+    pub fn report_data(&self) -> RaTlsResult<[u8; 64]> {
+        let digest = sha256(&self.public_key_der()?);
+        let mut report_data = [0u8; 64];
+        report_data[..digest.len()].copy_from_slice(&digest);
+        Ok(report_data)
+    }
+}
+
+/// Build a self-signed certificate for `keypair` carrying `quote` and `ias_report`
+/// inside the [`RA_TLS_QUOTE_OID`] extension, binding the attestation to this TLS
+/// session's key.
+pub fn build_self_signed_cert(
+    keypair: &RaTlsKeyPair,
+    quote: &Quote,
+    ias_report: &IasVerificationReport,
+) -> RaTlsResult<Vec<u8>> {
+    let extension_value = bincode::serialize(&(quote, ias_report))?;
+    let cert = X509Cert::new_self_signed(
+        &keypair.signing_key,
+        "CN=sgx-enclave",
+        &[(RA_TLS_QUOTE_OID, extension_value.as_slice())],
+    )?;
+    Ok(cert.to_der()?)
+}
+
+/// Pull the quote and IAS verification report back out of an RA-TLS certificate's
+/// extension.
+pub fn extract_quote(cert: &X509Cert) -> RaTlsResult<(Quote, IasVerificationReport)> {
+    let extension_value = cert
+        .extension(RA_TLS_QUOTE_OID)?
+        .ok_or(crate::error::RaTlsError::MissingQuoteExtension)?;
+    Ok(bincode::deserialize(extension_value)?)
+}
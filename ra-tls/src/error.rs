@@ -0,0 +1,40 @@
+#[derive(Debug, thiserror::Error)]
+pub enum RaTlsError {
+    #[error("certificate does not carry an RA-TLS quote extension")]
+    MissingQuoteExtension,
+
+    #[error("certificate report_data does not match the attested public key")]
+    ReportDataMismatch,
+
+    #[error("quote measurements do not match the expected sigstruct")]
+    MeasurementMismatch,
+
+    #[error("IAS verification report signature is invalid")]
+    ReportSignatureInvalid,
+
+    #[error("IAS verification report could not be parsed: {0}")]
+    MalformedReportBody(#[from] serde_json::Error),
+
+    #[error("IAS verification report's quote body does not match the certificate's embedded quote")]
+    ReportQuoteMismatch,
+
+    #[error("IAS reports this quote's status as untrusted: {0}")]
+    QuoteStatusNotTrusted(String),
+
+    #[error("enclave is running in debug mode")]
+    EnclaveInDebugMode,
+
+    #[error("attestation error: {0}")]
+    Attestation(String),
+
+    #[error("sgx crypto error: {0}")]
+    SgxCrypto(#[from] sgx_crypto::error::Error),
+
+    #[error("bincode error: {0}")]
+    Bincode(#[from] bincode::Error),
+
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+pub type RaTlsResult<T> = Result<T, RaTlsError>;
@@ -0,0 +1,57 @@
+use ra_common::msg::Quote;
+
+use crate::cert::IasVerificationReport;
+use crate::error::{RaTlsError, RaTlsResult};
+
+const IAS_REPORT_URL: &str =
+    "https://api.trustedservices.intel.com/sgx/dev/attestation/v4/report";
+
+/// Submit `quote` to IAS directly and return the signed verification report, so that an
+/// RA-TLS cert can be built without relaying through a separate SP service.
+pub async fn fetch_verification_report(
+    quote: &Quote,
+    subscription_key: &str,
+) -> RaTlsResult<IasVerificationReport> {
+    let client = reqwest::Client::new();
+    let response = client
+        .post(IAS_REPORT_URL)
+        .header("Ocp-Apim-Subscription-Key", subscription_key)
+        .json(&serde_json::json!({ "isvEnclaveQuote": base64::encode(&quote[..]) }))
+        .send()
+        .await
+        .map_err(|e| RaTlsError::Attestation(e.to_string()))?;
+
+    let signature = base64::decode(
+        response
+            .headers()
+            .get("X-IASReport-Signature")
+            .ok_or_else(|| RaTlsError::Attestation("missing X-IASReport-Signature".into()))?
+            .to_str()
+            .map_err(|e| RaTlsError::Attestation(e.to_string()))?,
+    )
+    .map_err(|e| RaTlsError::Attestation(e.to_string()))?;
+
+    let signing_cert_chain = percent_encoding::percent_decode_str(
+        response
+            .headers()
+            .get("X-IASReport-Signing-Certificate")
+            .ok_or_else(|| {
+                RaTlsError::Attestation("missing X-IASReport-Signing-Certificate".into())
+            })?
+            .to_str()
+            .map_err(|e| RaTlsError::Attestation(e.to_string()))?,
+    )
+    .collect::<Vec<u8>>();
+
+    let report_body = response
+        .bytes()
+        .await
+        .map_err(|e| RaTlsError::Attestation(e.to_string()))?
+        .to_vec();
+
+    Ok(IasVerificationReport {
+        signature,
+        signing_cert_chain,
+        report_body,
+    })
+}
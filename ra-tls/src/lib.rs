@@ -0,0 +1,18 @@
+//! RA-TLS: embed an SGX quote and its IAS verification report inside a self-signed
+//! X.509 certificate, so that remote attestation can ride over a standard TLS
+//! handshake instead of the hand-rolled MSG0-MSG4 protocol in `ra-client`/`ra-sp`.
+
+pub mod cert;
+pub mod error;
+pub mod ias;
+pub mod policy;
+#[cfg(feature = "rustls")]
+pub mod rustls_verifier;
+pub mod verifier;
+
+pub use cert::{build_self_signed_cert, IasVerificationReport, RaTlsKeyPair, RA_TLS_QUOTE_OID};
+pub use error::{RaTlsError, RaTlsResult};
+pub use policy::MeasurementPolicy;
+#[cfg(feature = "rustls")]
+pub use rustls_verifier::{AttestedClientCertVerifier, AttestedServerCertVerifier};
+pub use verifier::RaTlsVerifier;
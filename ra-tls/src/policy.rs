@@ -0,0 +1,80 @@
+use sgxs::sigstruct::Sigstruct;
+
+use crate::error::{RaTlsError, RaTlsResult};
+
+/// The measurements an RA-TLS verifier is willing to accept, mirroring the
+/// `quote_trust_options`/`pse_trust_options` allowlists `SpConfig` already uses for the
+/// legacy MSG0-MSG4 flow. Built from one or more loaded `Sigstruct`s so a verifier can
+/// accept a known set of enclave builds rather than a single exact measurement.
+pub struct MeasurementPolicy {
+    pub mrenclave_allowlist: Vec<[u8; 32]>,
+    pub mrsigner_allowlist: Vec<[u8; 32]>,
+    pub isvprodid: u16,
+    pub isvsvn_threshold: u16,
+    pub reject_debug_enclaves: bool,
+    /// IAS `isvEnclaveQuoteStatus` values other than `OK` that should still be trusted
+    /// (e.g. `GROUP_OUT_OF_DATE`), mirroring `SpConfig::quote_trust_options`.
+    pub quote_trust_options: Vec<String>,
+    /// As `quote_trust_options`, but for `pseManifestStatus`.
+    pub pse_trust_options: Option<Vec<String>>,
+}
+
+impl MeasurementPolicy {
+    /// Accept exactly the MRENCLAVE, MRSIGNER and ISVPRODID recorded in `sigstruct` and
+    /// any ISVSVN at or above its own, rejecting debug-mode enclaves and any quote/PSE
+    /// manifest status other than `OK` — the common case of
+    /// trusting one known-good enclave build with no operator-configured allowances.
+    pub fn from_sigstruct(sigstruct: &Sigstruct) -> Self {
+        let mrsigner = sgx_crypto::digest::sha256(&sigstruct.modulus[..]);
+        Self {
+            mrenclave_allowlist: vec![sigstruct.enclavehash],
+            mrsigner_allowlist: vec![mrsigner],
+            isvprodid: sigstruct.isvprodid,
+            isvsvn_threshold: sigstruct.isvsvn,
+            reject_debug_enclaves: true,
+            quote_trust_options: Vec::new(),
+            pse_trust_options: None,
+        }
+    }
+
+    /// Whether an IAS `isvEnclaveQuoteStatus` of `status` should be trusted: always
+    /// `OK`, or explicitly allowlisted via `quote_trust_options`.
+    pub fn is_quote_status_trusted(&self, status: &str) -> bool {
+        status == "OK" || self.quote_trust_options.iter().any(|s| s == status)
+    }
+
+    /// As [`Self::is_quote_status_trusted`], but for `pseManifestStatus`.
+    pub fn is_pse_manifest_trusted(&self, status: &str) -> bool {
+        status == "OK"
+            || self
+                .pse_trust_options
+                .as_ref()
+                .map(|options| options.iter().any(|s| s == status))
+                .unwrap_or(false)
+    }
+
+    pub fn check(
+        &self,
+        mrenclave: &[u8],
+        mrsigner: &[u8],
+        isvprodid: u16,
+        isvsvn: u16,
+        is_debug: bool,
+    ) -> RaTlsResult<()> {
+        let mrenclave_ok = self
+            .mrenclave_allowlist
+            .iter()
+            .any(|allowed| allowed[..] == *mrenclave);
+        let mrsigner_ok = self
+            .mrsigner_allowlist
+            .iter()
+            .any(|allowed| allowed[..] == *mrsigner);
+        if !mrenclave_ok || !mrsigner_ok || isvprodid != self.isvprodid || isvsvn < self.isvsvn_threshold {
+            return Err(RaTlsError::MeasurementMismatch);
+        }
+        if self.reject_debug_enclaves && is_debug {
+            return Err(RaTlsError::EnclaveInDebugMode);
+        }
+        Ok(())
+    }
+}
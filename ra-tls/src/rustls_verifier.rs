@@ -0,0 +1,150 @@
+use std::sync::Arc;
+
+use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+use rustls::server::danger::{ClientCertVerified, ClientCertVerifier};
+use rustls::{DigitallySignedStruct, DistinguishedName, SignatureScheme};
+use rustls::pki_types::{CertificateDer, ServerName, UnixTime};
+
+use crate::error::RaTlsError;
+use crate::verifier::RaTlsVerifier;
+
+/// A `rustls` [`ServerCertVerifier`] that only completes a handshake with a peer
+/// presenting a genuine, correctly-measured RA-TLS certificate (see the `ra-tls` crate
+/// root for the certificate format). Plug this into a `rustls::ClientConfig` to talk
+/// directly to an attested enclave without the bespoke `ra-client`/`ra-sp` protocol.
+pub struct AttestedServerCertVerifier {
+    inner: Arc<RaTlsVerifier>,
+    provider: Arc<rustls::crypto::CryptoProvider>,
+}
+
+impl AttestedServerCertVerifier {
+    pub fn new(verifier: RaTlsVerifier) -> Self {
+        Self {
+            inner: Arc::new(verifier),
+            provider: Arc::new(rustls::crypto::ring::default_provider()),
+        }
+    }
+}
+
+impl ServerCertVerifier for AttestedServerCertVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        _server_name: &ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        self.inner
+            .verify(end_entity.as_ref())
+            .map(|()| ServerCertVerified::assertion())
+            .map_err(ra_tls_error_to_rustls)
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        rustls::crypto::verify_tls12_signature(
+            message,
+            cert,
+            dss,
+            &self.provider.signature_verification_algorithms,
+        )
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        rustls::crypto::verify_tls13_signature(
+            message,
+            cert,
+            dss,
+            &self.provider.signature_verification_algorithms,
+        )
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.provider
+            .signature_verification_algorithms
+            .supported_schemes()
+    }
+}
+
+/// The mutual-attestation counterpart of [`AttestedServerCertVerifier`]: plug into a
+/// `rustls::ServerConfig` to only accept client connections that themselves present a
+/// valid RA-TLS certificate, so two enclaves can attest each other over one handshake.
+pub struct AttestedClientCertVerifier {
+    inner: Arc<RaTlsVerifier>,
+    provider: Arc<rustls::crypto::CryptoProvider>,
+}
+
+impl AttestedClientCertVerifier {
+    pub fn new(verifier: RaTlsVerifier) -> Self {
+        Self {
+            inner: Arc::new(verifier),
+            provider: Arc::new(rustls::crypto::ring::default_provider()),
+        }
+    }
+}
+
+impl ClientCertVerifier for AttestedClientCertVerifier {
+    fn root_hint_subjects(&self) -> &[DistinguishedName] {
+        &[]
+    }
+
+    fn verify_client_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        _now: UnixTime,
+    ) -> Result<ClientCertVerified, rustls::Error> {
+        self.inner
+            .verify(end_entity.as_ref())
+            .map(|()| ClientCertVerified::assertion())
+            .map_err(ra_tls_error_to_rustls)
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        rustls::crypto::verify_tls12_signature(
+            message,
+            cert,
+            dss,
+            &self.provider.signature_verification_algorithms,
+        )
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        rustls::crypto::verify_tls13_signature(
+            message,
+            cert,
+            dss,
+            &self.provider.signature_verification_algorithms,
+        )
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.provider
+            .signature_verification_algorithms
+            .supported_schemes()
+    }
+}
+
+fn ra_tls_error_to_rustls(e: RaTlsError) -> rustls::Error {
+    rustls::Error::General(e.to_string())
+}
@@ -0,0 +1,158 @@
+use std::convert::TryInto;
+use byteorder::{LittleEndian, ReadBytesExt};
+use serde::Deserialize;
+use sgx_crypto::certificate::X509Cert;
+use sgx_crypto::digest::sha256;
+use sgx_crypto::rsa::RsaPublicKey;
+
+use crate::cert::{extract_quote, IasVerificationReport};
+use crate::error::{RaTlsError, RaTlsResult};
+use crate::policy::MeasurementPolicy;
+
+/// The parts of an IAS verification report body we need: the quote it was computed
+/// over (so we can bind it to the cert-embedded quote) and the trust verdict for it.
+/// IAS returns camelCase keys, same as `ra_sp::ias::AttestationResult`.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct IasReportBody {
+    isv_enclave_quote_status: String,
+    isv_enclave_quote_body: String,
+    pse_manifest_status: Option<String>,
+}
+
+/// Verifies RA-TLS certificates: parses the embedded quote and IAS verification report
+/// out of the [`crate::cert::RA_TLS_QUOTE_OID`] extension, checks the report was really
+/// signed by Intel, binds the report to the embedded quote, checks the quote's
+/// measurements against `policy`, and checks the quote's `report_data` is bound to the
+/// certificate's own public key.
+pub struct RaTlsVerifier {
+    pub policy: MeasurementPolicy,
+    pub ias_root_cert: X509Cert,
+}
+
+impl RaTlsVerifier {
+    pub fn new(policy: MeasurementPolicy, ias_root_cert: X509Cert) -> Self {
+        Self {
+            policy,
+            ias_root_cert,
+        }
+    }
+
+    /// Verify a DER-encoded RA-TLS leaf certificate end to end. Returns `Ok(())` only if
+    /// the cert is self-consistent, the IAS report is genuinely Intel's and reports this
+    /// exact quote as trusted, and the measurements it attests to satisfy `self.policy`.
+    pub fn verify(&self, cert_der: &[u8]) -> RaTlsResult<()> {
+        let cert = X509Cert::new_from_der(cert_der)?;
+        let (quote, ias_report) = extract_quote(&cert)?;
+
+        let report_body = self.verify_ias_report(&ias_report)?;
+        let attested_quote = self.verify_quote_binding(&report_body, quote.as_ref())?;
+        self.verify_measurements(&attested_quote)?;
+        self.verify_report_data(&cert, &attested_quote)?;
+        Ok(())
+    }
+
+    /// Check the report body was really signed by Intel, and return it parsed.
+    fn verify_ias_report(&self, ias_report: &IasVerificationReport) -> RaTlsResult<IasReportBody> {
+        let signing_cert = X509Cert::new_from_pem(&ias_report.signing_cert_chain)?;
+        signing_cert
+            .verify_chain(&self.ias_root_cert)
+            .map_err(|_| RaTlsError::ReportSignatureInvalid)?;
+
+        let public_key = RsaPublicKey::from_cert(&signing_cert)?;
+        public_key
+            .verify_pkcs1_sha256(&ias_report.report_body, &ias_report.signature)
+            .map_err(|_| RaTlsError::ReportSignatureInvalid)?;
+
+        Ok(serde_json::from_slice(&ias_report.report_body)?)
+    }
+
+    /// Check the report's verdict for `quote` is trusted per `self.policy`'s
+    /// `quote_trust_options`/`pse_trust_options` allowlists, and that the quote body IAS
+    /// actually attested is byte-for-byte the one embedded in the certificate — without
+    /// this, a genuinely Intel-signed report for a *different* quote could be paired
+    /// with an arbitrary attacker-chosen quote blob.
+    fn verify_quote_binding(&self, report_body: &IasReportBody, quote: &[u8]) -> RaTlsResult<Vec<u8>> {
+        if !self
+            .policy
+            .is_quote_status_trusted(&report_body.isv_enclave_quote_status)
+        {
+            return Err(RaTlsError::QuoteStatusNotTrusted(
+                report_body.isv_enclave_quote_status.clone(),
+            ));
+        }
+        if let Some(pse_manifest_status) = &report_body.pse_manifest_status {
+            if !self.policy.is_pse_manifest_trusted(pse_manifest_status) {
+                return Err(RaTlsError::QuoteStatusNotTrusted(pse_manifest_status.clone()));
+            }
+        }
+
+        let attested_quote = base64::decode(&report_body.isv_enclave_quote_body)
+            .map_err(|_| RaTlsError::ReportQuoteMismatch)?;
+        if !quote_starts_with_attested_body(quote, &attested_quote) {
+            return Err(RaTlsError::ReportQuoteMismatch);
+        }
+        Ok(attested_quote)
+    }
+
+    fn verify_measurements(&self, quote: &[u8]) -> RaTlsResult<()> {
+        let mrenclave = &quote[112..144];
+        let mrsigner = &quote[176..208];
+        let isvprodid = (&quote[304..306]).read_u16::<LittleEndian>().unwrap();
+        let isvsvn = (&quote[306..308]).read_u16::<LittleEndian>().unwrap();
+        let attributes = &quote[96..104];
+        let is_debug = attributes[0] & 0x02 != 0;
+
+        self.policy
+            .check(mrenclave, mrsigner, isvprodid, isvsvn, is_debug)
+    }
+
+    fn verify_report_data(&self, cert: &X509Cert, quote: &[u8]) -> RaTlsResult<()> {
+        let expected: [u8; 32] = sha256(&cert.public_key_der()?);
+        let actual: [u8; 32] = (&quote[368..400]).try_into().unwrap();
+        if expected != actual {
+            return Err(RaTlsError::ReportDataMismatch);
+        }
+        Ok(())
+    }
+}
+
+/// IAS's `isvEnclaveQuoteBody` is only the 432-byte quote header + report body; the
+/// certificate embeds the complete quote the enclave requested, EPID signature and all.
+/// So the report attests to a *prefix* of the embedded quote, not the whole thing.
+fn quote_starts_with_attested_body(quote: &[u8], attested_quote_body: &[u8]) -> bool {
+    quote.len() >= attested_quote_body.len() && quote[..attested_quote_body.len()] == *attested_quote_body
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn attested_body_matching_quote_prefix_is_accepted() {
+        let mut quote = vec![0u8; 432];
+        quote[112] = 0xAB; // somewhere inside the attested body
+        quote.extend_from_slice(&[0xFF; 64]); // the EPID signature, which isvEnclaveQuoteBody leaves out
+        let attested_quote_body = quote[..432].to_vec();
+
+        assert!(quote_starts_with_attested_body(&quote, &attested_quote_body));
+    }
+
+    #[test]
+    fn attested_body_longer_than_quote_is_rejected() {
+        let quote = vec![0u8; 200];
+        let attested_quote_body = vec![0u8; 432];
+
+        assert!(!quote_starts_with_attested_body(&quote, &attested_quote_body));
+    }
+
+    #[test]
+    fn attested_body_not_matching_quote_prefix_is_rejected() {
+        let mut quote = vec![0u8; 432];
+        quote[0] = 0x01;
+        let mut attested_quote_body = quote.clone();
+        attested_quote_body[0] = 0x02;
+
+        assert!(!quote_starts_with_attested_body(&quote, &attested_quote_body));
+    }
+}